@@ -0,0 +1,12 @@
+//! 几何计算的小工具函数，供 [`crate::mpc`] 和 [`crate::path`] 等模块共用。
+
+/// 把角度归一化到 `(-π, π]`
+pub(crate) fn wrap_to_pi(angle: f32) -> f32 {
+    use std::f32::consts::PI;
+    let wrapped = (angle + PI).rem_euclid(2.0 * PI) - PI;
+    if wrapped <= -PI {
+        wrapped + 2.0 * PI
+    } else {
+        wrapped
+    }
+}
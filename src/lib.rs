@@ -10,7 +10,7 @@ mod predict;
 
 pub use chassis::*;
 pub use model::Pm1Model;
-pub use optimizer::Optimizer;
+pub use optimizer::{Optimizer, ProfilePhases, SCurvePlanner};
 pub use predict::Pm1Predictor;
 
 #[cfg(feature = "motor")]
@@ -19,6 +19,39 @@ mod motor;
 #[cfg(feature = "motor")]
 pub use motor::Motor;
 
+#[cfg(feature = "motor")]
+mod pid;
+
+#[cfg(feature = "motor")]
+pub use pid::{Pid, WheelPidController};
+
+#[cfg(feature = "odometry")]
+mod geometry;
+
+#[cfg(feature = "odometry")]
+mod odometry;
+
+#[cfg(feature = "odometry")]
+pub use odometry::Odometry;
+
+#[cfg(feature = "odometry")]
+mod mpc;
+
+#[cfg(feature = "odometry")]
+pub use mpc::{Limits, Mpc, ReferencePoint, Weights};
+
+#[cfg(feature = "odometry")]
+mod path;
+
+#[cfg(feature = "odometry")]
+pub use path::{Path, Projection};
+
+#[cfg(feature = "mecanum")]
+mod mecanum;
+
+#[cfg(feature = "mecanum")]
+pub use mecanum::{MecanumModel, Velocity3, Wheels4};
+
 /// 类阿卡曼物理模型
 #[derive(Clone, Copy, PartialEq, Debug)]
 #[repr(C)]
@@ -0,0 +1,154 @@
+use chassis::ChassisModel;
+
+/// 3 自由度刚体速度：纵向、横向平移速度及自转角速度
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Velocity3 {
+    /// 纵向速度，单位 m/s
+    pub vx: f32,
+    /// 横向速度，单位 m/s
+    pub vy: f32,
+    /// 自转角速度，单位 rad/s
+    pub w: f32,
+}
+
+/// 四轮独立轮速：左前、右前、左后、右后
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Wheels4 {
+    pub fl: f32,
+    pub fr: f32,
+    pub rl: f32,
+    pub rr: f32,
+}
+
+/// 麦克纳姆轮（全向）底盘模型
+///
+/// 描述 O 形布置的四个麦克纳姆轮，在 [`Velocity3`]（3 自由度车体速度）与
+/// [`Wheels4`]（四轮角速度）之间转换。启用 `odometry` 特性时，还提供
+/// `velocity_to_odometry`/`wheels_to_odometry`，复用 [`crate::Odometry`]
+/// 的累加管线，使全向平台的速度、轮速换算也能产生可累加的里程计。
+/// 预测器（`Pm1Predictor`）目前仍只针对类阿卡曼状态，尚未随本次改动
+/// 泛化到 `Velocity3`/`Wheels4`。
+#[derive(Clone)]
+pub struct MecanumModel {
+    /// 轴距一半（前后轮中心到车体中心的纵向距离），单位 m
+    pub lx: f32,
+    /// 轮距一半（左右轮中心到车体中心的横向距离），单位 m
+    pub ly: f32,
+    /// 轮半径，单位 m
+    pub wheel: f32,
+}
+
+impl MecanumModel {
+    /// 新建麦克纳姆轮底盘模型
+    #[inline]
+    pub fn new(lx: f32, ly: f32, wheel: f32) -> Self {
+        Self { lx, ly, wheel }
+    }
+
+    /// 逆运动学：车体速度 -> 四轮角速度
+    ///
+    /// `ω_i = (vx ∓ vy ∓ (lx+ly)·w) / r`，四轮符号分别为
+    /// `fl:(+,-,-)`、`fr:(+,+,+)`、`rl:(+,+,-)`、`rr:(+,-,+)`。
+    pub fn velocity_to_wheels(&self, velocity: Velocity3) -> Wheels4 {
+        let Velocity3 { vx, vy, w } = velocity;
+        let k = (self.lx + self.ly) * w;
+        Wheels4 {
+            fl: (vx - vy - k) / self.wheel,
+            fr: (vx + vy + k) / self.wheel,
+            rl: (vx + vy - k) / self.wheel,
+            rr: (vx - vy + k) / self.wheel,
+        }
+    }
+
+    /// 正运动学：四轮角速度 -> 车体速度
+    ///
+    /// 对逆运动学方程组取最小二乘解，等价于四轮贡献的平均值。
+    pub fn wheels_to_velocity(&self, wheels: Wheels4) -> Velocity3 {
+        let Wheels4 { fl, fr, rl, rr } = wheels;
+        Velocity3 {
+            vx: self.wheel * (fl + fr + rl + rr) / 4.0,
+            vy: self.wheel * (-fl + fr + rl - rr) / 4.0,
+            w: self.wheel * (-fl + fr - rl + rr) / (4.0 * (self.lx + self.ly)),
+        }
+    }
+}
+
+impl ChassisModel for MecanumModel {
+    type State = Velocity3;
+}
+
+#[cfg(feature = "odometry")]
+mod o {
+    use super::{MecanumModel, Velocity3, Wheels4};
+    use crate::odometry::Odometry;
+    use nalgebra::{Isometry2, Vector2};
+
+    /// 定义里程计从 `Velocity3` 转成 `Odometry`
+    ///
+    /// 对车体坐标系下恒定的 `(vx, vy, w)` 在单位时间内积分，推广
+    /// [`Odometry`] 已有的 `From<Velocity>`（`vy = 0` 时与其一致）：
+    /// `w = 0` 时位移为 `(vx, vy)`；否则为 `R(w)` 在 `[0, 1]` 上的
+    /// 积分作用在 `(vx, vy)` 上，即 `((vx·sinw - vy·(1-cosw))/w, (vx·(1-cosw) + vy·sinw)/w)`。
+    impl From<Velocity3> for Odometry {
+        fn from(vel: Velocity3) -> Self {
+            let Velocity3 { vx, vy, w } = vel;
+            let a = w.abs();
+            let s = (vx * vx + vy * vy).sqrt();
+
+            let pose = if a < f32::EPSILON {
+                Isometry2::new(Vector2::new(vx, vy), w)
+            } else {
+                let (sin, cos) = w.sin_cos();
+                Isometry2::new(
+                    Vector2::new((vx * sin - vy * (1.0 - cos)) / w, (vx * (1.0 - cos) + vy * sin) / w),
+                    w,
+                )
+            };
+
+            Self { s, a, pose }
+        }
+    }
+
+    impl MecanumModel {
+        /// 车体速度换算到里程计
+        pub fn velocity_to_odometry(&self, velocity: Velocity3) -> Odometry {
+            velocity.into()
+        }
+
+        /// 四轮角速度换算到里程计
+        pub fn wheels_to_odometry(&self, wheels: Wheels4) -> Odometry {
+            self.wheels_to_velocity(wheels).into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+
+    #[inline]
+    fn float_equal(a: f32, b: f32) -> bool {
+        (a - b).abs() <= 1e-5
+    }
+
+    #[test]
+    fn test_velocity_to_wheels_round_trip() {
+        let model = MecanumModel::new(0.2, 0.15, 0.05);
+        let velocities = [
+            Velocity3 { vx: 0.0, vy: 0.0, w: 0.0 },
+            Velocity3 { vx: 0.5, vy: 0.0, w: 0.0 },
+            Velocity3 { vx: 0.0, vy: 0.3, w: 0.0 },
+            Velocity3 { vx: 0.4, vy: -0.2, w: 0.5 },
+            Velocity3 { vx: -0.3, vy: 0.1, w: -0.4 },
+        ];
+
+        for velocity in velocities {
+            let wheels = model.velocity_to_wheels(velocity);
+            let restore = model.wheels_to_velocity(wheels);
+            assert!(float_equal(velocity.vx, restore.vx), "{:?} != {:?}", velocity.vx, restore.vx);
+            assert!(float_equal(velocity.vy, restore.vy), "{:?} != {:?}", velocity.vy, restore.vy);
+            assert!(float_equal(velocity.w, restore.w), "{:?} != {:?}", velocity.w, restore.w);
+        }
+    }
+}
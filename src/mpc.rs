@@ -0,0 +1,258 @@
+use crate::{geometry::wrap_to_pi, model::ChassisModel, odometry::Odometry, optimizer::step_limited, Physical, Velocity};
+use nalgebra::{DMatrix, DVector, Isometry2, Matrix3, Matrix3x2, Vector2, Vector3};
+use std::time::Duration;
+
+/// 参考轨迹上的一个采样点
+///
+/// 由位姿 `pose` 和该处的参考速度 `velocity` 组成，预测时域上的每一步都在其中一个采样点附近线性化。
+#[derive(Clone, Copy, Debug)]
+pub struct ReferencePoint {
+    pub pose: Isometry2<f32>,
+    pub velocity: Velocity,
+}
+
+/// 代价函数权重：状态误差权重 `q`（`[ex, ey, eθ]`）与控制量权重 `r`（`[v, w]`）
+#[derive(Clone, Copy, Debug)]
+pub struct Weights {
+    pub q: [f32; 3],
+    pub r: [f32; 2],
+}
+
+/// 控制量约束：最大线速度、最大角速度及舵轮每周期最大步进量
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// 最大线速度，单位 m/s
+    pub v_max: f32,
+    /// 最大角速度，单位 rad/s
+    pub w_max: f32,
+    /// 舵轮每个控制周期的最大步进量，单位 rad（复用 [`step_limited`] 做输出限幅）
+    pub rudder_step: f32,
+}
+
+/// 轨迹跟踪模型预测控制器
+///
+/// 在预测时域 `N`、离散步长 `DT` 上，将独轮车运动学模型 `x_{k+1} = x_k + [v cosθ, v sinθ, w]·DT`
+/// 沿参考轨迹逐点线性化，得到误差状态 `e_k = x_k - x_ref,k` 的线性传播模型，
+/// 并在速度、角速度约束下求解有限时域二次规划
+/// `min Σ e_kᵀQe_k + Σ u_kᵀRu_k`，将时域内第一步最优 `(v, w)` 换算回 [`Physical`]，
+/// 再按舵轮步进量对输出的舵角做限幅。
+#[derive(Clone)]
+pub struct Mpc {
+    model: ChassisModel,
+    /// 预测时域步数 N
+    horizon: usize,
+    /// 离散步长，单位 s
+    dt: f32,
+    /// 状态误差权重 `[ex, ey, eθ]`
+    q: Vector3<f32>,
+    /// 控制量权重 `[v, w]`
+    r: Vector2<f32>,
+    /// 最大线速度，单位 m/s
+    v_max: f32,
+    /// 最大角速度，单位 rad/s
+    w_max: f32,
+    /// 舵轮每个控制周期的最大步进量，单位 rad（复用 [`step_limited`] 做输出限幅）
+    rudder_step: f32,
+}
+
+impl Mpc {
+    /// 构造轨迹跟踪控制器
+    ///
+    /// - `model`：用于将求解得到的线、角速度换算回 [`Physical`] 的底盘模型
+    /// - `horizon`：预测时域步数 N
+    /// - `dt`：预测步长 DT
+    /// - `weights`：状态误差与控制量的权重
+    /// - `limits`：线、角速度及舵轮步进约束
+    #[inline]
+    pub fn new(model: ChassisModel, horizon: usize, dt: Duration, weights: Weights, limits: Limits) -> Self {
+        Self {
+            model,
+            horizon,
+            dt: dt.as_secs_f32(),
+            q: Vector3::from(weights.q),
+            r: Vector2::from(weights.r),
+            v_max: limits.v_max,
+            w_max: limits.w_max,
+            rudder_step: limits.rudder_step,
+        }
+    }
+
+    /// 求解一步最优控制量
+    ///
+    /// 给定参考轨迹 `reference`（至少覆盖预测时域）、当前里程计 `odometry` 和当前控制量 `current`，
+    /// 求解有限时域二次规划，返回下一个控制周期的目标 [`Physical`]。
+    pub fn solve(&self, reference: &[ReferencePoint], odometry: &Odometry, current: Physical) -> Physical {
+        let n = self.horizon.min(reference.len());
+        if n == 0 {
+            return Physical::RELEASED;
+        }
+
+        let x0 = error_state(&odometry.pose, &reference[0].pose);
+        let (a, b) = self.linearize(reference, n);
+        let (phi, gamma) = Self::condense(&a, &b, n);
+
+        let q_big = Self::block_diag(&self.q, n);
+        let r_big = Self::block_diag(&self.r, n);
+
+        let h = gamma.transpose() * &q_big * &gamma + r_big;
+        let f = gamma.transpose() * &q_big * phi * x0;
+
+        let u = self.projected_gradient(&h, &f, n);
+        let velocity = Velocity { v: u[0], w: u[1] };
+
+        let mut physical = self.model.velocity_to_physical(velocity);
+        physical.rudder = step_limited(current.rudder, self.rudder_step, physical.rudder);
+        physical
+    }
+
+    /// 沿参考轨迹逐点线性化，得到每一步的状态矩阵 `A_k` 和控制矩阵 `B_k`
+    fn linearize(&self, reference: &[ReferencePoint], n: usize) -> (Vec<Matrix3<f32>>, Vec<Matrix3x2<f32>>) {
+        let dt = self.dt;
+        let mut a = Vec::with_capacity(n);
+        let mut b = Vec::with_capacity(n);
+        for point in &reference[..n] {
+            let v_r = point.velocity.v;
+            let (sin, cos) = point.pose.rotation.angle().sin_cos();
+            #[rustfmt::skip]
+            a.push(Matrix3::new(
+                1.0, 0.0, -v_r * sin * dt,
+                0.0, 1.0,  v_r * cos * dt,
+                0.0, 0.0,  1.0,
+            ));
+            #[rustfmt::skip]
+            b.push(Matrix3x2::new(
+                cos * dt, 0.0,
+                sin * dt, 0.0,
+                0.0,      dt,
+            ));
+        }
+        (a, b)
+    }
+
+    /// 条件化（condensing）：把递推的误差传播 `e_k = A_{k-1}e_{k-1} + B_{k-1}u_{k-1}` 展开成
+    /// 关于初始误差 `x0` 和堆叠控制量 `u` 的显式矩阵形式 `e = Φx0 + Γu`
+    fn condense(a: &[Matrix3<f32>], b: &[Matrix3x2<f32>], n: usize) -> (DMatrix<f32>, DMatrix<f32>) {
+        let mut phi = DMatrix::<f32>::zeros(3 * n, 3);
+        let mut gamma = DMatrix::<f32>::zeros(3 * n, 2 * n);
+
+        let mut row = Vec::<Matrix3x2<f32>>::with_capacity(n);
+        let mut phi_k = Matrix3::<f32>::identity();
+        for (k, (a_k, b_k)) in a.iter().zip(b).enumerate() {
+            phi_k = a_k * phi_k;
+            phi.fixed_view_mut::<3, 3>(3 * k, 0).copy_from(&phi_k);
+
+            for block in row.iter_mut() {
+                *block = a_k * *block;
+            }
+            row.push(*b_k);
+            for (j, block) in row.iter().enumerate() {
+                gamma.fixed_view_mut::<3, 2>(3 * k, 2 * j).copy_from(block);
+            }
+        }
+        (phi, gamma)
+    }
+
+    /// 把逐步权重对角堆叠成时域上的分块对角矩阵
+    fn block_diag<const D: usize>(weight: &nalgebra::SVector<f32, D>, n: usize) -> DMatrix<f32> {
+        let mut m = DMatrix::<f32>::zeros(D * n, D * n);
+        for k in 0..n {
+            for i in 0..D {
+                m[(D * k + i, D * k + i)] = weight[i];
+            }
+        }
+        m
+    }
+
+    /// 在速度、角速度约束下，用投影梯度法求解条件化后的二次规划 `min 0.5uᵀHu + fᵀu`
+    ///
+    /// 步长取 `1 / L`，`L` 是 `H` 谱范数（最大特征值）的估计，而非其最大对角元——
+    /// `H` 是稠密矩阵，对角元可能远小于谱范数，用对角元做步长会导致不收敛甚至发散。
+    fn projected_gradient(&self, h: &DMatrix<f32>, f: &DVector<f32>, n: usize) -> DVector<f32> {
+        const ITERATIONS: usize = 50;
+
+        let step = 1.0 / Self::spectral_norm_estimate(h);
+
+        let mut u = DVector::<f32>::zeros(2 * n);
+        for _ in 0..ITERATIONS {
+            let gradient = h * &u + f;
+            u -= step * gradient;
+            for k in 0..n {
+                u[2 * k] = u[2 * k].clamp(-self.v_max, self.v_max);
+                u[2 * k + 1] = u[2 * k + 1].clamp(-self.w_max, self.w_max);
+            }
+        }
+        u
+    }
+
+    /// 用幂迭代估计对称正定矩阵 `h` 的谱范数（最大特征值）
+    fn spectral_norm_estimate(h: &DMatrix<f32>) -> f32 {
+        const ITERATIONS: usize = 20;
+
+        let mut v = DVector::<f32>::from_element(h.nrows(), 1.0);
+        for _ in 0..ITERATIONS {
+            let hv = h * &v;
+            let norm = hv.norm();
+            if norm <= f32::EPSILON {
+                return f32::EPSILON;
+            }
+            v = hv / norm;
+        }
+        (h * &v).norm().max(f32::EPSILON)
+    }
+}
+
+/// 误差状态 `e = x - x_ref = [ex, ey, eθ]`
+fn error_state(pose: &Isometry2<f32>, reference: &Isometry2<f32>) -> DVector<f32> {
+    let d = pose.translation.vector - reference.translation.vector;
+    let e_theta = wrap_to_pi(pose.rotation.angle() - reference.rotation.angle());
+    DVector::from_column_slice(&[d.x, d.y, e_theta])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 约束远未触发时，`Mpc::solve` 应收敛到无约束二次规划的闭式解。
+    ///
+    /// 取 `N = 1`、参考位姿为原点且朝向 0，此时 `A0 = I`、`B0` 只有两个非零元，
+    /// 误差代价与控制代价在 `v`、`w` 上解耦，闭式解为
+    /// `v* = -(dt·qx·ex) / (qx·dt² + rv)`，`w* = -(dt·qθ·eθ) / (qθ·dt² + rw)`。
+    #[test]
+    fn test_solve_matches_closed_form_when_unconstrained() {
+        let model = ChassisModel::default();
+        let dt = Duration::from_millis(50);
+        let weights = Weights {
+            q: [5.0, 5.0, 2.0],
+            r: [0.1, 0.1],
+        };
+        let limits = Limits {
+            v_max: 10.0,
+            w_max: 10.0,
+            rudder_step: 10.0,
+        };
+        let mpc = Mpc::new(model.clone(), 1, dt, weights, limits);
+
+        let reference = [ReferencePoint {
+            pose: Isometry2::identity(),
+            velocity: Velocity { v: 0.0, w: 0.0 },
+        }];
+        let odometry = Odometry {
+            s: 0.0,
+            a: 0.0,
+            pose: Isometry2::new(Vector2::new(0.03, 0.0), 0.01),
+        };
+
+        let physical = mpc.solve(&reference, &odometry, Physical::ZERO);
+
+        let t = dt.as_secs_f32();
+        let (qx, q_theta) = (5.0_f32, 2.0_f32);
+        let (rv, rw) = (0.1_f32, 0.1_f32);
+        let (ex, e_theta) = (0.03_f32, 0.01_f32);
+        let v_star = -(t * qx * ex) / (qx * t * t + rv);
+        let w_star = -(t * q_theta * e_theta) / (q_theta * t * t + rw);
+        let expected = model.velocity_to_physical(Velocity { v: v_star, w: w_star });
+
+        assert!((physical.speed - expected.speed).abs() < 1e-3);
+        assert!((physical.rudder - expected.rudder).abs() < 1e-3);
+    }
+}
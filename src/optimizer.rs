@@ -4,22 +4,105 @@ use std::{
     time::Duration,
 };
 
+/// 梯形/S 形速度规划的分段用时：加速、匀速、减速，单位 s
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProfilePhases {
+    pub accelerate: f32,
+    pub cruise: f32,
+    pub decelerate: f32,
+}
+
 /// 针对最大速度的优化器
 #[derive(Clone, Copy, Debug)]
 pub struct Optimizer {
     angular_attenuation: f32, // 舵转角衰减因子
     speed_step: f32,          // 每个控制周期速度的最大步进量（m/s）
+    length: f32,              // 前后轮距离，单位 m，用于由舵角换算转弯半径
+    max_lateral_acceleration: f32, // 最大侧向加速度，单位 m/s^2
 }
 
 impl Optimizer {
     /// - `angular_attenuation`: 舵转角衰减因子，用于额外限制转身速度。取值限制在 [0,1]，越大转身越慢
     /// - `acceleration`: 加速度，单位 m/s^2
     /// - `period`: 参考控制周期，单位 s
+    /// - `length`: 前后轮距离，单位 m，与底盘模型的 `length` 一致
+    /// - `max_lateral_acceleration`: 最大侧向加速度，单位 m/s^2，用于按转弯半径限速
     #[inline]
-    pub fn new(angular_attenuation: f32, acceleration: f32, period: Duration) -> Self {
+    pub fn new(
+        angular_attenuation: f32,
+        acceleration: f32,
+        period: Duration,
+        length: f32,
+        max_lateral_acceleration: f32,
+    ) -> Self {
         Self {
             angular_attenuation,
             speed_step: acceleration * period.as_secs_f32(),
+            length,
+            max_lateral_acceleration,
+        }
+    }
+
+    /// 梯形速度规划
+    ///
+    /// 给定到终点的剩余距离 `remaining`、当前速度 `v0`、巡航速度 `v_max`、加速度 `a`
+    /// 和控制周期 `period`，规划加速、匀速、减速三段式速度曲线，返回下一个控制周期的目标速度。
+    ///
+    /// 减速到停止所需的距离为 `d_dec = v0²/(2a)`：一旦 `d_dec ≥ remaining`，
+    /// 说明必须立即减速，按 `sqrt(max(0, 2a·remaining))` 规划；否则朝 `v_max` 加速，
+    /// 但每周期变化量不超过 `a·period`（复用 [`step_limited`]）。
+    pub fn plan_trapezoidal(remaining: f32, v0: f32, v_max: f32, a: f32, period: Duration) -> f32 {
+        let d_dec = v0 * v0 / (2.0 * a);
+        if d_dec >= remaining {
+            f32::sqrt(f32::max(0.0, 2.0 * a * remaining))
+        } else {
+            step_limited(v0, a * period.as_secs_f32(), v_max)
+        }
+    }
+
+    /// 梯形速度规划的分段用时预览
+    ///
+    /// 假定以加速度 `a` 从 `v0` 加速到 `v_max`、匀速行驶，再以 `a` 减速到 0，恰好在 `remaining`
+    /// 处停止，返回加速、匀速、减速三段各自的用时。当剩余距离不足以达到 `v_max` 时，
+    /// 退化为先加速后减速、不经过匀速段的三角形曲线。
+    ///
+    /// 当 `v0` 已经超过 `v_max` 时没有加速段：先以 `a` 减速到 `v_max`（若距离不够，
+    /// 直接一路减速到停），再正常巡航、最终减速到 0；`decelerate` 是这两段减速时长之和。
+    pub fn trapezoidal_phases(remaining: f32, v0: f32, v_max: f32, a: f32) -> ProfilePhases {
+        if v0 > v_max {
+            let d_stop = v0 * v0 / (2.0 * a);
+            return if d_stop >= remaining {
+                ProfilePhases {
+                    accelerate: 0.0,
+                    cruise: 0.0,
+                    decelerate: v0 / a,
+                }
+            } else {
+                let d_brake = (v0 * v0 - v_max * v_max) / (2.0 * a);
+                let d_dec = v_max * v_max / (2.0 * a);
+                ProfilePhases {
+                    accelerate: 0.0,
+                    cruise: (remaining - d_brake - d_dec) / v_max,
+                    decelerate: (v0 - v_max) / a + v_max / a,
+                }
+            };
+        }
+
+        let d_acc = (v_max * v_max - v0 * v0) / (2.0 * a);
+        let d_dec = v_max * v_max / (2.0 * a);
+        if d_acc + d_dec >= remaining {
+            let v_peak = f32::sqrt(f32::max(0.0, a * remaining + v0 * v0 / 2.0));
+            ProfilePhases {
+                accelerate: (v_peak - v0) / a,
+                cruise: 0.0,
+                decelerate: v_peak / a,
+            }
+        } else {
+            ProfilePhases {
+                accelerate: (v_max - v0) / a,
+                cruise: (remaining - d_acc - d_dec) / v_max,
+                decelerate: v_max / a,
+            }
         }
     }
 
@@ -40,12 +123,58 @@ impl Optimizer {
             f32::max(0.0,1.0 - diff / width) *
             // 基于现象的限速：转弯不要太快
             ((1.0 - target.rudder.abs() / FRAC_PI_2) * (1.0 - self.angular_attenuation) + self.angular_attenuation);
+
+            // 基于物理的限速：转弯半径决定的最大侧向加速度，v² / r ≤ a_lat_max
+            if target.rudder != 0.0 {
+                let r = self.length / target.rudder.tan();
+                let v_lat_max = f32::sqrt(self.max_lateral_acceleration * r.abs());
+                speed = speed.clamp(-v_lat_max, v_lat_max);
+            }
         }
         // 基于现象的限速：加速不要太快
         step_limited(current.speed, self.speed_step, speed)
     }
 }
 
+/// S 形（限制加加速度）速度规划器
+///
+/// 与一次性调用的 [`Optimizer::plan_trapezoidal`] 不同，本规划器按控制周期滚动调用，
+/// 内部记录自上次 [`SCurvePlanner::reset`] 以来经过的时间：允许的加速度上限从 0
+/// 按 `jerk` 线性爬升，耗时 `a_max / jerk`（爬升时间，即“blend”），爬升结束后维持在
+/// `a_max`；每一步都用这个随时间变化的加速度上限复用梯形规划求解目标速度。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SCurvePlanner {
+    elapsed: f32,
+}
+
+impl SCurvePlanner {
+    /// 新建规划器，从零开始爬升加速度
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按控制周期滚动调用一步
+    ///
+    /// - `remaining`：到终点的剩余距离
+    /// - `v0`：当前速度
+    /// - `v_max`：巡航速度
+    /// - `a_max`：爬升结束后的巡航加速度
+    /// - `jerk`：加加速度限制，决定爬升到 `a_max` 所需时间 `a_max / jerk`
+    /// - `period`：控制周期
+    pub fn plan(&mut self, remaining: f32, v0: f32, v_max: f32, a_max: f32, jerk: f32, period: Duration) -> f32 {
+        self.elapsed += period.as_secs_f32();
+        let ramped = f32::min(a_max, jerk * self.elapsed);
+        Optimizer::plan_trapezoidal(remaining, v0, v_max, ramped, period)
+    }
+
+    /// 把已经过的爬升时间清零，下一次 [`SCurvePlanner::plan`] 重新从 0 加速度爬升
+    #[inline]
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
 /// 有限步进
 ///
 /// 从当前状态 `current` 向目标状态 `target` 逼近，但最多变化 step
@@ -60,3 +189,50 @@ pub(crate) fn step_limited(current: f32, step: f32, target: f32) -> f32 {
         Some(Greater) => f32::min(current + step, target),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trapezoidal_phases_when_faster_than_cruise() {
+        // v0 已经超过 v_max：不应该出现负的加速段用时
+        let phases = Optimizer::trapezoidal_phases(10.0, 2.0, 1.0, 1.0);
+        assert!(phases.accelerate >= 0.0);
+        assert!(phases.cruise >= 0.0);
+        assert!(phases.decelerate >= 0.0);
+        assert!((phases.accelerate - 0.0).abs() < 1e-6);
+        assert!((phases.cruise - 8.0).abs() < 1e-4);
+        assert!((phases.decelerate - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_trapezoidal_phases_when_faster_than_cruise_and_distance_too_short() {
+        // v0 超过 v_max 且剩余距离不足以先减到 v_max：全程只有一段减速
+        let phases = Optimizer::trapezoidal_phases(1.0, 2.0, 1.0, 1.0);
+        assert!(phases.accelerate >= 0.0);
+        assert!(phases.cruise >= 0.0);
+        assert!(phases.decelerate >= 0.0);
+        assert!((phases.decelerate - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_s_curve_planner_reaches_full_acceleration_after_blend_time() {
+        let period = Duration::from_millis(40);
+        let (a_max, jerk) = (1.0_f32, 2.0_f32);
+        let blend = a_max / jerk; // 0.5s
+
+        let mut planner = SCurvePlanner::new();
+        let mut v = 0.0;
+        let mut elapsed = 0.0;
+        while elapsed < blend {
+            v = planner.plan(100.0, v, 10.0, a_max, jerk, period);
+            elapsed += period.as_secs_f32();
+        }
+
+        // 爬升时间过后，单步增量应达到满额巡航加速度，而不是永远被钉在 jerk*period
+        let before = v;
+        let after = planner.plan(100.0, before, 10.0, a_max, jerk, period);
+        assert!((after - before - a_max * period.as_secs_f32()).abs() < 1e-4);
+    }
+}
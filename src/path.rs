@@ -0,0 +1,137 @@
+use crate::geometry::wrap_to_pi;
+use nalgebra::{Isometry2, Vector2};
+
+/// 参考路径
+///
+/// 由一系列位姿（折线）构成，供反馈控制器在跟踪前求出机器人相对路径的误差。
+#[derive(Clone, Debug)]
+pub struct Path(pub Vec<Isometry2<f32>>);
+
+/// 位姿到路径的最近点投影结果
+#[derive(Clone, Copy, Debug)]
+pub struct Projection {
+    /// 最近线段起点在路径中的下标
+    pub segment: usize,
+    /// 在该线段上的插值比例，∈ [0, 1]
+    pub t: f32,
+    /// 有符号横向（侧偏）误差：机器人在路径切线左侧为正，单位 m
+    pub cross_track: f32,
+    /// 航向误差：机器人朝向与路径切线方向之差，归一化到 (-π, π]
+    pub yaw_err: f32,
+}
+
+/// 逐段扫描时的候选最近点
+struct Candidate {
+    dist_sq: f32,
+    segment: usize,
+    t: f32,
+    tangent: Vector2<f32>,
+    to_robot: Vector2<f32>,
+}
+
+impl Path {
+    /// 由一串位姿构造参考路径
+    #[inline]
+    pub fn new(poses: Vec<Isometry2<f32>>) -> Self {
+        Self(poses)
+    }
+
+    /// 把 `pose` 投影到路径上，返回最近点所在线段、插值比例及横向、航向误差
+    ///
+    /// 逐段计算 `t = clamp(((pose - p_i)·(p_{i+1}-p_i)) / |p_{i+1}-p_i|², 0, 1)`，
+    /// 取距离最近的线段，再用切线与最近点到机器人向量的二维叉积求有符号横向误差。
+    /// 长度为零的退化线段（相邻两点重合）无法定义切线，直接跳过；
+    /// 路径不足两个点、或所有线段都退化时，返回 `None`。
+    pub fn project(&self, pose: &Isometry2<f32>) -> Option<Projection> {
+        let p = pose.translation.vector;
+
+        let mut nearest: Option<Candidate> = None;
+        for (i, pair) in self.0.windows(2).enumerate() {
+            let p0 = pair[0].translation.vector;
+            let p1 = pair[1].translation.vector;
+            let tangent = p1 - p0;
+            let len_sq = tangent.norm_squared();
+            if len_sq <= f32::EPSILON {
+                continue;
+            }
+
+            let t = ((p - p0).dot(&tangent) / len_sq).clamp(0.0, 1.0);
+            let closest = p0 + tangent * t;
+            let to_robot = p - closest;
+            let dist_sq = to_robot.norm_squared();
+            if nearest.as_ref().is_none_or(|c| dist_sq < c.dist_sq) {
+                nearest = Some(Candidate {
+                    dist_sq,
+                    segment: i,
+                    t,
+                    tangent,
+                    to_robot,
+                });
+            }
+        }
+
+        let Candidate {
+            segment,
+            t,
+            tangent,
+            to_robot,
+            ..
+        } = nearest?;
+        let cross_track = (tangent.x * to_robot.y - tangent.y * to_robot.x) / tangent.norm();
+        let tangent_angle = tangent.y.atan2(tangent.x);
+        let yaw_err = wrap_to_pi(pose.rotation.angle() - tangent_angle);
+
+        Some(Projection {
+            segment,
+            t,
+            cross_track,
+            yaw_err,
+        })
+    }
+}
+
+impl Projection {
+    /// 跟踪误差是否在允许范围内：横向误差不超过 `pos_limit`，航向误差不超过 `yaw_limit`
+    #[inline]
+    pub fn admissible(&self, pos_limit: f32, yaw_limit: f32) -> bool {
+        self.cross_track.abs() <= pos_limit && self.yaw_err.abs() <= yaw_limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_onto_straight_segment() {
+        let path = Path::new(vec![
+            Isometry2::new(Vector2::new(0.0, 0.0), 0.0),
+            Isometry2::new(Vector2::new(10.0, 0.0), 0.0),
+        ]);
+        // 机器人在路径中点正上方 1m 处，朝向与路径一致
+        let pose = Isometry2::new(Vector2::new(5.0, 1.0), 0.0);
+
+        let projection = path.project(&pose).unwrap();
+        assert_eq!(projection.segment, 0);
+        assert!((projection.t - 0.5).abs() < 1e-6);
+        assert!((projection.cross_track - 1.0).abs() < 1e-6);
+        assert!(projection.yaw_err.abs() < 1e-6);
+        assert!(projection.admissible(2.0, 0.1));
+        assert!(!projection.admissible(0.5, 0.1));
+    }
+
+    #[test]
+    fn test_project_skips_degenerate_segment() {
+        // 前两点重合，退化线段不应让横向误差算出 NaN
+        let path = Path::new(vec![
+            Isometry2::new(Vector2::new(0.0, 0.0), 0.0),
+            Isometry2::new(Vector2::new(0.0, 0.0), 0.0),
+            Isometry2::new(Vector2::new(10.0, 0.0), 0.0),
+        ]);
+        let pose = Isometry2::new(Vector2::new(0.0, 0.0), 0.0);
+
+        let projection = path.project(&pose).unwrap();
+        assert_eq!(projection.segment, 1);
+        assert!(!projection.cross_track.is_nan());
+    }
+}
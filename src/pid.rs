@@ -0,0 +1,119 @@
+use crate::{Motor, Wheels};
+use std::time::Duration;
+
+/// 位置式 PID 控制器
+///
+/// 带积分限幅（anti-windup）和输出限幅，供 [`WheelPidController`] 对左右轮各自独立使用。
+#[derive(Clone, Copy, Debug)]
+pub struct Pid {
+    kp: f32,
+    ki: f32,
+    kd: f32,
+    /// 积分限幅，防止长时间偏差导致积分饱和
+    integral_limit: f32,
+    /// 输出限幅
+    output_limit: f32,
+    integral: f32,
+    last_error: f32,
+}
+
+impl Pid {
+    /// 构造 PID 控制器
+    #[inline]
+    pub fn new(kp: f32, ki: f32, kd: f32, integral_limit: f32, output_limit: f32) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            integral_limit,
+            output_limit,
+            integral: 0.0,
+            last_error: 0.0,
+        }
+    }
+
+    /// 按周期 `period`（单位 s）根据偏差 `error = target - measured` 更新一步，返回修正量
+    pub fn update(&mut self, error: f32, period: f32) -> f32 {
+        self.integral = (self.integral + error * period).clamp(-self.integral_limit, self.integral_limit);
+        let derivative = (error - self.last_error) / period;
+        self.last_error = error;
+        (self.kp * error + self.ki * self.integral + self.kd * derivative)
+            .clamp(-self.output_limit, self.output_limit)
+    }
+
+    /// 清空积分项和微分项的历史偏差
+    #[inline]
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last_error = 0.0;
+    }
+}
+
+/// 左右轮独立闭环调速控制器
+///
+/// 每个控制周期，把编码器脉冲增量经 [`Motor::pluses_to_rad`] 换算成实测轮角速度，
+/// 与来自运动学前馈（`velocity_to_wheels`）的目标轮速作差，分别经左右轮独立 PID
+/// 修正，得到补偿了阻力、打滑等未建模误差后的轮速指令。
+#[derive(Clone)]
+pub struct WheelPidController {
+    left: Pid,
+    right: Pid,
+}
+
+impl WheelPidController {
+    /// 给定左右轮各自的 PID 参数，构造调速控制器
+    #[inline]
+    pub fn new(left: Pid, right: Pid) -> Self {
+        Self { left, right }
+    }
+
+    /// 根据目标轮速 `target`、编码器本周期脉冲增量 `pulses`（左、右）、解码用的
+    /// `motor` 模型和控制周期 `period`，返回修正后的轮速指令
+    pub fn update(&mut self, target: Wheels, pulses: (i32, i32), motor: &Motor, period: Duration) -> Wheels {
+        let dt = period.as_secs_f32();
+        let measured = Wheels {
+            left: motor.pluses_to_rad(pulses.0) / dt,
+            right: motor.pluses_to_rad(pulses.1) / dt,
+        };
+        Wheels {
+            left: target.left + self.left.update(target.left - measured.left, dt),
+            right: target.right + self.right.update(target.right - measured.right, dt),
+        }
+    }
+
+    /// 重置左右轮 PID 的积分项和微分历史
+    #[inline]
+    pub fn reset(&mut self) {
+        self.left.reset();
+        self.right.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pid_converges_to_target() {
+        let mut pid = Pid::new(1.0, 0.5, 0.05, 10.0, 10.0);
+        let period = 0.02;
+        let target = 2.0;
+        let mut measured = 0.0;
+        for _ in 0..1000 {
+            let correction = pid.update(target - measured, period);
+            measured += correction * period;
+        }
+        assert!((measured - target).abs() < 1e-2, "measured = {measured}");
+    }
+
+    #[test]
+    fn test_pid_saturates_output_and_integral() {
+        let mut pid = Pid::new(1.0, 1.0, 0.0, 1.0, 2.0);
+        let period = 0.02;
+        for _ in 0..1000 {
+            let output = pid.update(100.0, period);
+            assert!(output <= 2.0, "output = {output}");
+            assert!(pid.integral <= 1.0, "integral = {}", pid.integral);
+        }
+    }
+}
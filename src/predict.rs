@@ -11,6 +11,8 @@ use std::time::Duration;
 #[derive(Clone)]
 pub struct Pm1Predictor {
     rudder_step: f32, // 后轮最大步进量，单位 rad
+    #[cfg(feature = "odometry")]
+    period: f32, // 控制周期，单位 s，仅 predict_horizon 滚动积分需要
     optimizer: Optimizer,
     /// 当前状态
     pub current: Physical,
@@ -44,6 +46,8 @@ impl Pm1Predictor {
     pub fn new(optimizer: Optimizer, period: Duration) -> Self {
         Self {
             rudder_step: period.as_secs_f32(),
+            #[cfg(feature = "odometry")]
+            period: period.as_secs_f32(),
             optimizer,
             current: Physical::ZERO,
             target: Physical::RELEASED,
@@ -61,11 +65,46 @@ impl Pm1Predictor {
     }
 }
 
+#[cfg(feature = "odometry")]
+impl Pm1Predictor {
+    /// 滚动预测
+    ///
+    /// 在当前目标 `target` 下，以控制周期向前滚动最多 `n` 步，返回每一步的控制量
+    /// 及累计里程计序列；一旦途中到达目标并停住，序列提前结束。
+    ///
+    /// 推演基于 `self` 的副本进行，不改变 `self` 当前的状态。每一步控制量经
+    /// `model.physical_to_velocity` 换算为速度、按控制周期折算成位移量后，
+    /// 复用 [`Odometry`] 已有的 `From<Velocity>` 与 `AddAssign` 累加成轨迹。
+    pub fn predict_horizon(
+        &self,
+        model: &crate::model::ChassisModel,
+        n: usize,
+    ) -> Vec<(Physical, crate::odometry::Odometry)> {
+        use crate::{odometry::Odometry, Velocity};
+
+        let mut rolled = self.clone();
+        let mut odometry = Odometry::ZERO;
+        let mut trajectory = Vec::with_capacity(n);
+        for _ in 0..n {
+            let Some(physical) = rolled.predict() else {
+                break;
+            };
+            let velocity = model.physical_to_velocity(physical);
+            odometry += Odometry::from(Velocity {
+                v: velocity.v * rolled.period,
+                w: velocity.w * rolled.period,
+            });
+            trajectory.push((physical, odometry));
+        }
+        trajectory
+    }
+}
+
 #[test]
 fn test_status_predictor() {
     // 打印出来看看
     const PERIOD: Duration = Duration::from_millis(40);
-    let mut pre = Pm1Predictor::new(Optimizer::new(0.5, 1.2, PERIOD), PERIOD);
+    let mut pre = Pm1Predictor::new(Optimizer::new(0.5, 1.2, PERIOD, 0.355, 2.0), PERIOD);
     pre.current = Physical {
         speed: 0.4,
         rudder: 0.0,